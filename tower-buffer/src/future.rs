@@ -0,0 +1,64 @@
+//! Future types
+
+use crate::Error;
+use futures::sync::oneshot;
+use futures::{Async, Future, Poll};
+
+/// Future returned by `Buffer`.
+pub struct ResponseFuture<T>
+where
+    T: Future,
+{
+    state: State<T>,
+}
+
+enum State<T>
+where
+    T: Future,
+{
+    Rx(oneshot::Receiver<Result<T::Item, T::Error>>),
+    Failed(Option<Error>),
+}
+
+impl<T> ResponseFuture<T>
+where
+    T: Future,
+{
+    pub(crate) fn new(rx: oneshot::Receiver<Result<T::Item, T::Error>>) -> Self {
+        ResponseFuture {
+            state: State::Rx(rx),
+        }
+    }
+
+    /// Immediately resolve to `err`, without ever reaching the worker.
+    pub(crate) fn failed(err: Error) -> Self {
+        ResponseFuture {
+            state: State::Failed(Some(err)),
+        }
+    }
+}
+
+impl<T> Future for ResponseFuture<T>
+where
+    T: Future,
+    T::Error: Into<Error>,
+{
+    type Item = T::Item;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.state {
+            State::Rx(ref mut rx) => match rx.poll() {
+                Ok(Async::Ready(Ok(v))) => Ok(Async::Ready(v)),
+                Ok(Async::Ready(Err(e))) => Err(e.into()),
+                Ok(Async::NotReady) => Ok(Async::NotReady),
+                // The worker's sender was dropped before replying, which
+                // means the worker task has stopped running.
+                Err(_) => Err(crate::Closed(()).into()),
+            },
+            State::Failed(ref mut err) => {
+                Err(err.take().expect("ResponseFuture polled after completion"))
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Tower middleware that buffers requests, decoupling `poll_ready` from the
+//! inner service and allowing a single service to be shared across many
+//! callers.
+
+#[macro_use]
+extern crate futures;
+extern crate tokio_executor;
+extern crate tower_service;
+
+use futures::future::Executor;
+use futures::sync::mpsc;
+use futures::sync::oneshot;
+use tower_service::Service;
+use std::{error::Error as StdError, fmt};
+
+mod future;
+mod message;
+mod worker;
+
+pub use crate::future::ResponseFuture;
+
+use crate::message::Message;
+use crate::worker::Worker;
+
+/// Adds a bounded buffer in front of an inner service.
+///
+/// `Buffer` makes a single, possibly non-`Clone`, non-`Sync` service usable
+/// from many callers by spawning a worker task that owns the inner service.
+/// `Buffer` itself is just a channel sender, so it is cheap to `Clone` and
+/// share across tasks. Its `poll_ready` reserves a slot in the bounded
+/// channel rather than polling the inner service directly, which decouples
+/// readiness from the service itself and smooths bursty load into it.
+pub struct Buffer<T, Request>
+where
+    T: Service<Request>,
+{
+    tx: mpsc::Sender<Message<Request, T::Future>>,
+}
+
+impl<T, Request> Clone for Buffer<T, Request>
+where
+    T: Service<Request>,
+{
+    fn clone(&self) -> Self {
+        Buffer {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+/// Error produced when spawning the worker task onto the default executor
+/// fails.
+pub type SpawnError = tokio_executor::SpawnError;
+
+type Error = Box<StdError + Send + Sync>;
+
+/// Error returned when the worker task has stopped running.
+#[derive(Debug)]
+pub(crate) struct Closed(());
+
+impl StdError for Closed {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for Closed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "buffer's worker closed")
+    }
+}
+
+/// Error returned when `call` is invoked without a reserved channel slot,
+/// i.e. without first waiting for `poll_ready` to report readiness.
+#[derive(Debug)]
+pub(crate) struct Full(());
+
+impl StdError for Full {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for Full {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "buffer full; `call` must not be invoked before `poll_ready` reports ready")
+    }
+}
+
+// ===== impl Buffer =====
+
+impl<T, Request> Buffer<T, Request>
+where
+    T: Service<Request> + Send + 'static,
+    T::Future: Send,
+    Request: Send + 'static,
+{
+    /// Spawn a new `Buffer` wrapping `service`. Its bounded channel holds up
+    /// to `capacity` queued requests, plus one guaranteed slot for each
+    /// `Buffer` handle.
+    pub fn new(service: T, capacity: usize) -> Result<Self, SpawnError> {
+        let (tx, rx) = mpsc::channel(capacity);
+        let worker = Worker::new(service, rx);
+
+        tokio_executor::DefaultExecutor::current().execute(worker)?;
+
+        Ok(Buffer { tx })
+    }
+}
+
+impl<T, Request> Service<Request> for Buffer<T, Request>
+where
+    T: Service<Request>,
+    T::Error: Into<Error>,
+{
+    type Response = T::Response;
+    type Error = Error;
+    type Future = ResponseFuture<T::Future>;
+
+    fn poll_ready(&mut self) -> futures::Poll<(), Self::Error> {
+        self.tx.poll_ready().map_err(|_| Closed(()).into())
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let (tx, rx) = oneshot::channel();
+
+        match self.tx.try_send(Message { request, tx }) {
+            Ok(()) => ResponseFuture::new(rx),
+            Err(ref e) if e.is_disconnected() => ResponseFuture::failed(Closed(()).into()),
+            Err(_) => ResponseFuture::failed(Full(()).into()),
+        }
+    }
+}
+
+// ==== mod tests ====
+
+#[cfg(test)]
+mod tests {
+    extern crate tokio;
+
+    use super::*;
+    use futures::future;
+
+    #[derive(Clone)]
+    struct Echo;
+
+    impl Service<&'static str> for Echo {
+        type Response = &'static str;
+        type Error = Error;
+        type Future = future::FutureResult<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self) -> futures::Poll<(), Self::Error> {
+            Ok(().into())
+        }
+
+        fn call(&mut self, request: &'static str) -> Self::Future {
+            future::ok(request)
+        }
+    }
+
+    type EchoMessage = Message<&'static str, <Echo as Service<&'static str>>::Future>;
+
+    #[test]
+    fn call_succeeds() {
+        let mut rt = tokio::runtime::current_thread::Runtime::new().unwrap();
+
+        // `Buffer::new` spawns its worker onto the default executor, so it
+        // must run inside the runtime's executor context.
+        let mut buffer = rt
+            .block_on(future::lazy(|| future::ok::<_, ()>(Buffer::new(Echo, 1).unwrap())))
+            .unwrap();
+
+        let res = rt.block_on(buffer.call("hi"));
+        assert_eq!(res.unwrap(), "hi");
+    }
+
+    #[test]
+    fn call_on_full_buffer_returns_full_error() {
+        let mut rt = tokio::runtime::current_thread::Runtime::new().unwrap();
+        let (tx, _rx) = mpsc::channel::<EchoMessage>(0);
+        let mut buffer: Buffer<Echo, &'static str> = Buffer { tx };
+
+        // Nothing is draining `_rx`, so this reserves the channel's only
+        // guaranteed slot.
+        let _first = buffer.call("one");
+
+        let res = rt.block_on(buffer.call("two"));
+        let err = res.unwrap_err();
+        assert!(err.downcast_ref::<Full>().is_some());
+    }
+
+    #[test]
+    fn call_after_worker_closed_returns_closed_error() {
+        let mut rt = tokio::runtime::current_thread::Runtime::new().unwrap();
+        let (tx, rx) = mpsc::channel::<EchoMessage>(1);
+        // Dropping the receiver simulates the worker task having stopped.
+        drop(rx);
+        let mut buffer: Buffer<Echo, &'static str> = Buffer { tx };
+
+        let res = rt.block_on(buffer.call("hi"));
+        let err = res.unwrap_err();
+        assert!(err.downcast_ref::<Closed>().is_some());
+    }
+}
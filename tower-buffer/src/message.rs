@@ -0,0 +1,12 @@
+use futures::sync::oneshot;
+use futures::Future;
+
+/// A request queued onto a `Buffer`'s channel, paired with the oneshot
+/// through which the worker reports back the result of calling it.
+pub(crate) struct Message<Request, F>
+where
+    F: Future,
+{
+    pub(crate) request: Request,
+    pub(crate) tx: oneshot::Sender<Result<F::Item, F::Error>>,
+}
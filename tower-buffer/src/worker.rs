@@ -0,0 +1,111 @@
+use crate::message::Message;
+use futures::stream::FuturesUnordered;
+use futures::sync::{mpsc, oneshot};
+use futures::{Async, Future, Poll, Stream};
+use tower_service::Service;
+
+/// The worker task that owns the inner service, dispatching queued requests
+/// to it and driving their response futures to completion concurrently,
+/// forwarding each result back through its oneshot.
+pub(crate) struct Worker<T, Request>
+where
+    T: Service<Request>,
+{
+    rx: mpsc::Receiver<Message<Request, T::Future>>,
+    service: T,
+    in_flight: FuturesUnordered<Flight<T::Future>>,
+}
+
+impl<T, Request> Worker<T, Request>
+where
+    T: Service<Request>,
+{
+    pub(crate) fn new(service: T, rx: mpsc::Receiver<Message<Request, T::Future>>) -> Self {
+        Worker {
+            rx,
+            service,
+            in_flight: FuturesUnordered::new(),
+        }
+    }
+}
+
+impl<T, Request> Future for Worker<T, Request>
+where
+    T: Service<Request>,
+{
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            // Drive every in-flight call towards completion, forwarding
+            // results through their oneshots as they finish, without
+            // blocking further dispatch on any single one of them.
+            loop {
+                match self.in_flight.poll() {
+                    Ok(Async::Ready(Some(()))) => continue,
+                    Ok(Async::Ready(None)) | Ok(Async::NotReady) => break,
+                    Err(()) => unreachable!("Flight::poll never errors"),
+                }
+            }
+
+            // Only dispatch a new request once the inner service itself
+            // reports readiness.
+            match self.service.poll_ready() {
+                Ok(Async::Ready(())) => {}
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Err(_) => return Err(()),
+            }
+
+            match try_ready!(self.rx.poll()) {
+                Some(Message { request, tx }) => {
+                    let inner = self.service.call(request);
+                    self.in_flight.push(Flight {
+                        inner,
+                        tx: Some(tx),
+                    });
+                }
+                None => {
+                    // All `Buffer` handles have been dropped; shut down
+                    // once every in-flight call has finished replying.
+                    if self.in_flight.is_empty() {
+                        return Ok(Async::Ready(()));
+                    }
+                    return Ok(Async::NotReady);
+                }
+            }
+        }
+    }
+}
+
+/// Drives a single dispatched call to completion and forwards its result
+/// through the caller's oneshot.
+struct Flight<F>
+where
+    F: Future,
+{
+    inner: F,
+    tx: Option<oneshot::Sender<Result<F::Item, F::Error>>>,
+}
+
+impl<F> Future for Flight<F>
+where
+    F: Future,
+{
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let result = match self.inner.poll() {
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Ok(Async::Ready(v)) => Ok(v),
+            Err(e) => Err(e),
+        };
+
+        if let Some(tx) = self.tx.take() {
+            let _ = tx.send(result);
+        }
+
+        Ok(Async::Ready(()))
+    }
+}
@@ -0,0 +1,46 @@
+//! Future types
+
+use crate::{Error, Overloaded};
+use futures::{Future, Poll};
+
+/// Future returned by `LoadShed`.
+#[derive(Debug)]
+pub struct ResponseFuture<T> {
+    state: State<T>,
+}
+
+#[derive(Debug)]
+enum State<T> {
+    Called(T),
+    Overloaded,
+}
+
+impl<T> ResponseFuture<T> {
+    pub(crate) fn called(inner: T) -> Self {
+        ResponseFuture {
+            state: State::Called(inner),
+        }
+    }
+
+    pub(crate) fn overloaded() -> Self {
+        ResponseFuture {
+            state: State::Overloaded,
+        }
+    }
+}
+
+impl<T> Future for ResponseFuture<T>
+where
+    T: Future,
+    T::Error: Into<Error>,
+{
+    type Item = T::Item;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.state {
+            State::Called(ref mut fut) => fut.poll().map_err(Into::into),
+            State::Overloaded => Err(Overloaded(()).into()),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Tower middleware that sheds load when the inner service isn't ready.
+
+extern crate futures;
+extern crate tower_service;
+
+use futures::{Async, Future, Poll};
+use tower_service::Service;
+use std::{error::Error as StdError, fmt};
+
+mod future;
+
+pub use crate::future::ResponseFuture;
+
+/// A `Service` that sheds load when the inner service isn't ready.
+///
+/// Instead of backpressuring callers while the inner service's `poll_ready`
+/// reports `NotReady`, `LoadShed` immediately fails calls made while the
+/// inner service is overloaded. `LoadShed::poll_ready` itself always
+/// reports `Ready`, so the backpressure is converted into a per-request
+/// error instead of propagating upstream.
+#[derive(Debug)]
+pub struct LoadShed<S> {
+    inner: S,
+    is_ready: bool,
+}
+
+type Error = Box<StdError + Send + Sync>;
+
+/// An error returned by `LoadShed` when the inner service is not ready to
+/// accept a request.
+#[derive(Debug)]
+pub struct Overloaded(());
+
+impl StdError for Overloaded {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for Overloaded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "service overloaded")
+    }
+}
+
+// ===== impl LoadShed =====
+
+impl<S> LoadShed<S> {
+    /// Wrap a service in `LoadShed`, converting its backpressure into an
+    /// error instead of making callers wait.
+    pub fn new(inner: S) -> Self {
+        LoadShed {
+            inner,
+            is_ready: false,
+        }
+    }
+
+    /// Get a reference to the inner service
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner service
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume `self`, returning the inner service
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, Request> Service<Request> for LoadShed<S>
+where
+    S: Service<Request>,
+    S::Error: Into<Error>,
+{
+    type Response = S::Response;
+    type Error = Error;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        // Remember whether the inner service was ready, but never
+        // backpressure the caller on it.
+        self.is_ready = match self.inner.poll_ready() {
+            Ok(Async::Ready(())) => true,
+            Ok(Async::NotReady) => false,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Async::Ready(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if self.is_ready {
+            // The caller must have called `poll_ready` first, which set
+            // this flag; clear it here so that the next `call` without a
+            // fresh `poll_ready` is shed by default.
+            self.is_ready = false;
+            ResponseFuture::called(self.inner.call(request))
+        } else {
+            ResponseFuture::overloaded()
+        }
+    }
+}
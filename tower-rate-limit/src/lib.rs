@@ -26,6 +26,19 @@ pub struct RateLimit<T> {
 pub struct Rate {
     num: u64,
     per: Duration,
+    mode: Mode,
+}
+
+/// How a `Rate` paces calls through a `RateLimit`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Mode {
+    /// Refills `num` calls all at once every `per`, so up to `2 * num`
+    /// calls can land back-to-back across a window boundary.
+    FixedWindow,
+    /// Refills continuously at `num / per` tokens per second, up to a
+    /// burst ceiling of `num`, giving a steady sustained rate instead of a
+    /// sawtooth.
+    TokenBucket,
 }
 
 /// The request has been rate limited
@@ -54,10 +67,17 @@ pub struct ResponseFuture<T> {
 enum State {
     // The service has hit its limit
     Limited(Delay),
+    // Fixed-window mode: `rem` calls remain until `until`.
     Ready {
         until: Instant,
         rem: u64,
     },
+    // Token-bucket mode: `tokens` are available, last topped up at
+    // `last_refill`.
+    TokenBucket {
+        tokens: f64,
+        last_refill: Instant,
+    },
 }
 
 impl<T> RateLimit<T> {
@@ -66,9 +86,15 @@ impl<T> RateLimit<T> {
     where
         T: Service<Request>,
     {
-        let state = State::Ready {
-            until: Instant::now(),
-            rem: rate.num,
+        let state = match rate.mode {
+            Mode::FixedWindow => State::Ready {
+                until: Instant::now(),
+                rem: rate.num,
+            },
+            Mode::TokenBucket => State::TokenBucket {
+                tokens: rate.num as f64,
+                last_refill: Instant::now(),
+            },
         };
 
         RateLimit {
@@ -95,7 +121,8 @@ impl<T> RateLimit<T> {
 }
 
 impl Rate {
-    /// Create a new rate
+    /// Create a new fixed-window rate: `num` calls are allowed per `per`,
+    /// all replenished at once when the window rolls over.
     ///
     /// # Panics
     ///
@@ -104,7 +131,29 @@ impl Rate {
         assert!(num > 0);
         assert!(per > Duration::from_millis(0));
 
-        Rate { num, per }
+        Rate {
+            num,
+            per,
+            mode: Mode::FixedWindow,
+        }
+    }
+
+    /// Create a new token-bucket rate: up to `num` calls may burst at
+    /// once, and the bucket refills continuously at `num` calls per `per`,
+    /// yielding a steady sustained rate instead of `new`'s sawtooth.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `num` or `per` is 0.
+    pub fn new_token_bucket(num: u64, per: Duration) -> Self {
+        assert!(num > 0);
+        assert!(per > Duration::from_millis(0));
+
+        Rate {
+            num,
+            per,
+            mode: Mode::TokenBucket,
+        }
     }
 }
 
@@ -118,22 +167,54 @@ where
     type Future = ResponseFuture<S::Future>;
 
     fn poll_ready(&mut self) -> Poll<(), Self::Error> {
-        match self.state {
-            State::Ready { .. } => return Ok(().into()),
-            State::Limited(ref mut sleep) => {
-                let res = sleep.poll()
-                    .map_err(|_| RateLimitError);
+        loop {
+            match self.state {
+                State::Ready { .. } => return Ok(().into()),
+                State::TokenBucket { tokens, last_refill } => {
+                    // Unlike fixed-window mode, a `TokenBucket` in `Ready`
+                    // state doesn't guarantee a token is actually
+                    // available; refill it here before answering, or this
+                    // would report `Ready` and then have `call` immediately
+                    // turn around and fail with `RateLimitError`.
+                    let now = Instant::now();
+                    let refill_rate = self.rate.num as f64 / duration_to_secs(self.rate.per);
+                    let elapsed = duration_to_secs(now.duration_since(last_refill));
+                    let tokens = (tokens + elapsed * refill_rate).min(self.rate.num as f64);
 
-                try_ready!(res);
-            }
-        }
+                    if tokens >= 1.0 {
+                        self.state = State::TokenBucket {
+                            tokens,
+                            last_refill: now,
+                        };
+                        return Ok(().into());
+                    }
 
-        self.state = State::Ready {
-            until: Instant::now() + self.rate.per,
-            rem: self.rate.num,
-        };
+                    let wait = secs_to_duration((1.0 - tokens) / refill_rate);
+                    self.state = State::Limited(Delay::new(now + wait));
+                }
+                State::Limited(ref mut sleep) => {
+                    let res = sleep.poll()
+                        .map_err(|_| RateLimitError);
+
+                    try_ready!(res);
+
+                    self.state = match self.rate.mode {
+                        Mode::FixedWindow => State::Ready {
+                            until: Instant::now() + self.rate.per,
+                            rem: self.rate.num,
+                        },
+                        // A `Limited` delay only ever waits for a single
+                        // token to become available.
+                        Mode::TokenBucket => State::TokenBucket {
+                            tokens: 1.0,
+                            last_refill: Instant::now(),
+                        },
+                    };
 
-        Ok(().into())
+                    return Ok(().into());
+                }
+            }
+        }
     }
 
     fn call(&mut self, request: Request) -> Self::Future {
@@ -162,6 +243,28 @@ where
                 let inner = Some(self.inner.call(request));
                 ResponseFuture { inner }
             }
+            State::TokenBucket { tokens, last_refill } => {
+                let now = Instant::now();
+                let refill_rate = self.rate.num as f64 / duration_to_secs(self.rate.per);
+                let elapsed = duration_to_secs(now.duration_since(last_refill));
+                let tokens = (tokens + elapsed * refill_rate).min(self.rate.num as f64);
+
+                if tokens >= 1.0 {
+                    self.state = State::TokenBucket {
+                        tokens: tokens - 1.0,
+                        last_refill: now,
+                    };
+
+                    let inner = Some(self.inner.call(request));
+                    ResponseFuture { inner }
+                } else {
+                    // Not enough in the bucket; wait until one token is
+                    // available.
+                    let wait = secs_to_duration((1.0 - tokens) / refill_rate);
+                    self.state = State::Limited(Delay::new(now + wait));
+                    ResponseFuture { inner: None }
+                }
+            }
             State::Limited(..) => {
                 ResponseFuture { inner: None }
             }
@@ -186,3 +289,101 @@ where
         }
     }
 }
+
+fn duration_to_secs(d: Duration) -> f64 {
+    d.as_secs() as f64 + f64::from(d.subsec_nanos()) / 1_000_000_000.0
+}
+
+fn secs_to_duration(secs: f64) -> Duration {
+    if secs <= 0.0 {
+        return Duration::from_secs(0);
+    }
+
+    let nanos = (secs * 1_000_000_000.0) as u64;
+    Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
+}
+
+// ==== mod tests ====
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_service::Service;
+
+    struct Echo;
+
+    impl Service<()> for Echo {
+        type Response = ();
+        type Error = Box<dyn std::error::Error + Send + Sync>;
+        type Future = futures::future::FutureResult<(), Self::Error>;
+
+        fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+            Ok(().into())
+        }
+
+        fn call(&mut self, _request: ()) -> Self::Future {
+            futures::future::ok(())
+        }
+    }
+
+    #[test]
+    fn duration_secs_round_trip() {
+        for secs in &[0.0, 0.001, 0.5, 1.0, 1.5, 10.0, 123.456] {
+            let d = secs_to_duration(*secs);
+            let back = duration_to_secs(d);
+            assert!(
+                (back - secs).abs() < 1e-6,
+                "{} round-tripped to {}",
+                secs,
+                back
+            );
+        }
+
+        assert_eq!(secs_to_duration(-1.0), Duration::from_secs(0));
+        assert_eq!(secs_to_duration(0.0), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn token_bucket_caps_burst_at_num_even_after_a_long_idle() {
+        let rate = Rate::new_token_bucket(4, Duration::from_secs(1));
+        let mut svc = RateLimit::new(Echo, rate);
+
+        // Simulate having been idle far longer than it takes to fully
+        // refill, so the bucket should cap at `num` rather than keep
+        // accumulating.
+        svc.state = State::TokenBucket {
+            tokens: 0.0,
+            last_refill: Instant::now() - Duration::from_secs(100),
+        };
+
+        svc.call(()).wait().expect("call");
+
+        match svc.state {
+            State::TokenBucket { tokens, .. } => {
+                assert_eq!(tokens, 3.0, "burst capped at num, minus the one consumed")
+            }
+            ref other => panic!("unexpected state: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn token_bucket_refills_in_proportion_to_elapsed_time() {
+        let rate = Rate::new_token_bucket(10, Duration::from_secs(1));
+        let mut svc = RateLimit::new(Echo, rate);
+
+        svc.state = State::TokenBucket {
+            tokens: 2.0,
+            last_refill: Instant::now() - Duration::from_millis(500),
+        };
+
+        svc.call(()).wait().expect("call");
+
+        match svc.state {
+            State::TokenBucket { tokens, .. } => {
+                // 2.0 starting + 0.5s * 10/s refilled - 1.0 consumed = 6.0
+                assert!((tokens - 6.0).abs() < 0.1, "tokens was {}", tokens);
+            }
+            ref other => panic!("unexpected state: {:?}", other),
+        }
+    }
+}
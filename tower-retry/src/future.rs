@@ -0,0 +1,118 @@
+//! Future types
+
+use crate::Policy;
+use futures::{Async, Future, Poll};
+use tower_service::Service;
+
+/// The future returned by a [`Retry`](crate::Retry) service.
+#[derive(Debug)]
+pub struct ResponseFuture<P, S, Request>
+where
+    S: Service<Request>,
+    P: Policy<Request, S::Response, S::Error>,
+{
+    request: Option<Request>,
+    policy: P,
+    service: S,
+    state: State<S::Future, P::Future, S::Response, S::Error>,
+}
+
+#[derive(Debug)]
+enum State<F, T, Response, Error> {
+    /// Polling the future from the inner service.
+    Called(F),
+    /// Polling the policy's future, which resolves once the next attempt
+    /// should be issued.
+    ///
+    /// If the policy's future resolves to `Err(())`, the result that led to
+    /// this retry attempt is returned to the caller as the final outcome,
+    /// rather than being discarded.
+    Waiting(T, Option<Result<Response, Error>>),
+    /// Waiting for the inner service to report readiness before the next
+    /// attempt is issued.
+    PollReady,
+}
+
+impl<P, S, Request> ResponseFuture<P, S, Request>
+where
+    S: Service<Request>,
+    P: Policy<Request, S::Response, S::Error>,
+{
+    pub(crate) fn new(policy: P, service: S, request: Option<Request>, future: S::Future) -> Self {
+        ResponseFuture {
+            request,
+            policy,
+            service,
+            state: State::Called(future),
+        }
+    }
+}
+
+impl<P, S, Request> Future for ResponseFuture<P, S, Request>
+where
+    S: Service<Request>,
+    P: Policy<Request, S::Response, S::Error>,
+{
+    type Item = S::Response;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let new_state = match self.state {
+                State::Called(ref mut future) => {
+                    let result = match future.poll() {
+                        Ok(Async::NotReady) => return Ok(Async::NotReady),
+                        Ok(Async::Ready(res)) => Ok(res),
+                        Err(err) => Err(err),
+                    };
+
+                    let retry = match self.request {
+                        // The request couldn't be cloned up-front, so there
+                        // is nothing left to retry the call with.
+                        None => None,
+                        Some(ref req) => match result {
+                            Ok(ref res) => self.policy.retry(req, Ok(res)),
+                            Err(ref err) => self.policy.retry(req, Err(err)),
+                        },
+                    };
+
+                    match retry {
+                        Some(fut) => State::Waiting(fut, Some(result)),
+                        None => return result.map(Async::Ready),
+                    }
+                }
+                State::Waiting(ref mut fut, ref mut pending) => match fut.poll() {
+                    Ok(Async::NotReady) => return Ok(Async::NotReady),
+                    Ok(Async::Ready(policy)) => {
+                        self.policy = policy;
+                        State::PollReady
+                    }
+                    Err(()) => {
+                        // The policy gave up on waiting for the next attempt;
+                        // return the result that triggered this retry instead
+                        // of issuing another call.
+                        let result = pending.take().expect("polled after completion");
+                        return result.map(Async::Ready);
+                    }
+                },
+                State::PollReady => {
+                    // Re-drive `poll_ready` before re-issuing the call, per
+                    // the `Service` contract; this is required for inner
+                    // services (e.g. `RateLimit`, `InFlightLimit`) whose
+                    // readiness is part of their own state machine.
+                    try_ready!(self.service.poll_ready());
+
+                    let request = self
+                        .request
+                        .take()
+                        .expect("retry issued without a cloned request");
+
+                    self.request = self.policy.clone_request(&request);
+                    State::Called(self.service.call(request))
+                }
+            };
+
+            self.state = new_state;
+        }
+    }
+}
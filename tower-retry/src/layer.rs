@@ -0,0 +1,35 @@
+use crate::{Policy, Retry};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Layering a `RetryLayer` onto a `Service` can never fail.
+#[derive(Debug)]
+pub enum Never {}
+
+/// Retry requests based on a given [`Policy`].
+#[derive(Debug, Clone)]
+pub struct RetryLayer<P> {
+    policy: P,
+}
+
+impl<P> RetryLayer<P> {
+    /// Create a new `RetryLayer` from a retry policy.
+    pub fn new(policy: P) -> Self {
+        RetryLayer { policy }
+    }
+}
+
+impl<P, S, Request> Layer<S, Request> for RetryLayer<P>
+where
+    P: Policy<Request, S::Response, S::Error> + Clone,
+    S: Service<Request> + Clone,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type LayerError = Never;
+    type Service = Retry<P, S>;
+
+    fn layer(&self, service: S) -> Result<Self::Service, Self::LayerError> {
+        Ok(Retry::new(self.policy.clone(), service))
+    }
+}
@@ -0,0 +1,231 @@
+//! Tower middleware that retries requests based on a user-supplied policy.
+//!
+//! Requests that fail, or that succeed but are otherwise unsatisfactory, can
+//! be re-driven according to a [`Policy`] implementation. The policy decides
+//! both *whether* a request should be retried and *when* the next attempt
+//! should begin, which makes it possible to express fixed-count retries,
+//! exponential backoff, or retry budgets.
+
+#[macro_use]
+extern crate futures;
+extern crate tower_layer;
+extern crate tower_service;
+
+use futures::Future;
+use tower_service::Service;
+
+mod future;
+mod layer;
+mod policy;
+
+pub use crate::future::ResponseFuture;
+pub use crate::layer::RetryLayer;
+pub use crate::policy::FiniteRetries;
+
+/// A policy which decides whether a request should be retried, and if so,
+/// when the next attempt should begin.
+pub trait Policy<Request, Response, Error>: Sized {
+    /// A future that resolves to the updated policy state once the next
+    /// attempt should begin.
+    ///
+    /// If this future resolves to `Err(())`, the retry is abandoned and the
+    /// result that triggered it (the one originally passed to [`retry`]) is
+    /// returned to the caller instead of a new attempt being issued.
+    ///
+    /// [`retry`]: Policy::retry
+    type Future: Future<Item = Self, Error = ()>;
+
+    /// Check the policy if a certain request should be retried.
+    ///
+    /// This method is passed a reference to the original request, and the
+    /// result of the inner service's call. If the request should be
+    /// retried, this returns a future that resolves once the next attempt
+    /// should be issued. Otherwise, it returns `None`, and the result is
+    /// returned to the caller.
+    fn retry(&self, req: &Request, result: Result<&Response, &Error>) -> Option<Self::Future>;
+
+    /// Tries to clone a request before being passed to the inner service.
+    ///
+    /// If the request cannot be cloned, return `None`. Retries will only be
+    /// attempted if the request can be cloned.
+    fn clone_request(&self, req: &Request) -> Option<Request>;
+}
+
+/// Configure retrying requests of "failed" responses.
+#[derive(Debug)]
+pub struct Retry<P, S> {
+    policy: P,
+    inner: S,
+}
+
+// ===== impl Retry =====
+
+impl<P, S> Retry<P, S> {
+    /// Retry the inner service depending on this [`Policy`].
+    pub fn new<Request>(policy: P, inner: S) -> Self
+    where
+        P: Policy<Request, S::Response, S::Error> + Clone,
+        S: Service<Request> + Clone,
+    {
+        Retry { policy, inner }
+    }
+
+    /// Get a reference to the inner service
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner service
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume `self`, returning the inner service
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<P, S> Clone for Retry<P, S>
+where
+    P: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Retry {
+            policy: self.policy.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<P, S, Request> Service<Request> for Retry<P, S>
+where
+    P: Policy<Request, S::Response, S::Error> + Clone,
+    S: Service<Request> + Clone,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = ResponseFuture<P, S, Request>;
+
+    fn poll_ready(&mut self) -> futures::Poll<(), Self::Error> {
+        self.inner.poll_ready()
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let cloned = self.policy.clone_request(&request);
+        let future = self.inner.call(request);
+
+        ResponseFuture::new(self.policy.clone(), self.inner.clone(), cloned, future)
+    }
+}
+
+// ==== mod tests ====
+
+#[cfg(test)]
+mod tests {
+    extern crate tokio;
+
+    use super::*;
+    use crate::policy::FiniteRetries;
+    use futures::future;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Clone)]
+    struct Mock {
+        results: Rc<RefCell<Vec<Result<&'static str, &'static str>>>>,
+    }
+
+    impl Mock {
+        fn new(results: Vec<Result<&'static str, &'static str>>) -> Self {
+            Mock {
+                results: Rc::new(RefCell::new(results)),
+            }
+        }
+    }
+
+    impl Service<&'static str> for Mock {
+        type Response = &'static str;
+        type Error = &'static str;
+        type Future = future::FutureResult<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self) -> futures::Poll<(), Self::Error> {
+            Ok(().into())
+        }
+
+        fn call(&mut self, _request: &'static str) -> Self::Future {
+            let result = self.results.borrow_mut().remove(0);
+            future::result(result)
+        }
+    }
+
+    /// A policy whose requests can never be cloned, so no call is ever
+    /// retried, even though the policy itself would otherwise allow it.
+    #[derive(Debug, Clone)]
+    struct NeverCloneable;
+
+    impl Policy<&'static str, &'static str, &'static str> for NeverCloneable {
+        type Future = future::FutureResult<Self, ()>;
+
+        fn retry(
+            &self,
+            _req: &&'static str,
+            result: Result<&&'static str, &&'static str>,
+        ) -> Option<Self::Future> {
+            if result.is_err() {
+                Some(future::ok(self.clone()))
+            } else {
+                None
+            }
+        }
+
+        fn clone_request(&self, _req: &&'static str) -> Option<&'static str> {
+            None
+        }
+    }
+
+    fn new_runtime() -> tokio::runtime::current_thread::Runtime {
+        tokio::runtime::current_thread::Runtime::new().unwrap()
+    }
+
+    #[test]
+    fn first_try_success() {
+        let mut rt = new_runtime();
+        let mock = Mock::new(vec![Ok("ok")]);
+        let mut svc = Retry::new(FiniteRetries::new(2), mock);
+
+        let res = rt.block_on(svc.call("req"));
+        assert_eq!(res, Ok("ok"));
+    }
+
+    #[test]
+    fn fail_then_retry_then_succeed() {
+        let mut rt = new_runtime();
+        let mock = Mock::new(vec![Err("retry me"), Ok("ok")]);
+        let mut svc = Retry::new(FiniteRetries::new(1), mock);
+
+        let res = rt.block_on(svc.call("req"));
+        assert_eq!(res, Ok("ok"));
+    }
+
+    #[test]
+    fn exhausts_finite_retries() {
+        let mut rt = new_runtime();
+        let mock = Mock::new(vec![Err("nope"), Err("nope")]);
+        let mut svc = Retry::new(FiniteRetries::new(1), mock);
+
+        let res = rt.block_on(svc.call("req"));
+        assert_eq!(res, Err("nope"));
+    }
+
+    #[test]
+    fn request_not_cloneable_is_not_retried() {
+        let mut rt = new_runtime();
+        let mock = Mock::new(vec![Err("nope")]);
+        let mut svc = Retry::new(NeverCloneable, mock);
+
+        let res = rt.block_on(svc.call("req"));
+        assert_eq!(res, Err("nope"));
+    }
+}
@@ -0,0 +1,59 @@
+//! Sample `Policy` implementations
+
+use crate::Policy;
+use futures::{Async, Future, Poll};
+
+/// A [`Policy`] that retries a fixed, maximum number of times.
+///
+/// Each clone of a `FiniteRetries` tracks its own remaining attempt count, so
+/// a single `FiniteRetries` can be reused as the starting policy for many
+/// requests.
+#[derive(Debug, Clone)]
+pub struct FiniteRetries {
+    max: usize,
+}
+
+impl FiniteRetries {
+    /// Create a new `FiniteRetries` policy that allows up to `max` retries.
+    pub fn new(max: usize) -> Self {
+        FiniteRetries { max }
+    }
+}
+
+/// The immediately-ready future returned by [`FiniteRetries::retry`].
+#[derive(Debug)]
+pub struct FiniteRetriesFuture {
+    remaining: usize,
+}
+
+impl Future for FiniteRetriesFuture {
+    type Item = FiniteRetries;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        Ok(Async::Ready(FiniteRetries {
+            max: self.remaining,
+        }))
+    }
+}
+
+impl<Request, Response, Error> Policy<Request, Response, Error> for FiniteRetries
+where
+    Request: Clone,
+{
+    type Future = FiniteRetriesFuture;
+
+    fn retry(&self, _req: &Request, result: Result<&Response, &Error>) -> Option<Self::Future> {
+        if result.is_ok() || self.max == 0 {
+            return None;
+        }
+
+        Some(FiniteRetriesFuture {
+            remaining: self.max - 1,
+        })
+    }
+
+    fn clone_request(&self, req: &Request) -> Option<Request> {
+        Some(req.clone())
+    }
+}
@@ -0,0 +1,108 @@
+//! Utilities and extension traits for `Service`.
+//!
+//! `ServiceExt` wraps the various middleware defined throughout this crate
+//! family behind fluent combinator methods, so that stacks can be composed
+//! as `svc.timeout(d).rate_limit(5, secs).concurrency_limit(10)` instead of
+//! nesting constructors by hand. It also provides `map` and `then`, for
+//! transforming a service's response type and for normalizing the
+//! heterogeneous error types produced by the other middleware.
+
+extern crate futures;
+extern crate tower_buffer;
+extern crate tower_in_flight_limit;
+extern crate tower_load_shed;
+extern crate tower_rate_limit;
+extern crate tower_service;
+extern crate tower_timeout;
+
+use std::time::Duration;
+use futures::Future;
+use tower_buffer::Buffer;
+use tower_in_flight_limit::InFlightLimit;
+use tower_load_shed::LoadShed;
+use tower_rate_limit::{Rate, RateLimit};
+use tower_service::Service;
+use tower_timeout::Timeout;
+
+mod map;
+mod then;
+
+pub use crate::map::{Map, MapFuture};
+pub use crate::then::{Then, ThenFuture};
+
+/// An extension trait for `Service`s that provides a variety of convenient
+/// adapters.
+pub trait ServiceExt<Request>: Service<Request> {
+    /// Limit the number of requests that can be issued over a period of
+    /// time to this service.
+    fn rate_limit(self, num: u64, per: Duration) -> RateLimit<Self>
+    where
+        Self: Sized,
+    {
+        RateLimit::new(self, Rate::new(num, per))
+    }
+
+    /// Fail requests that take longer than `duration` to complete.
+    fn timeout(self, duration: Duration) -> Timeout<Self>
+    where
+        Self: Sized,
+    {
+        Timeout::new(self, duration)
+    }
+
+    /// Limit the number of in-flight requests to `max`, exerting
+    /// backpressure on `poll_ready` once the limit is reached.
+    fn concurrency_limit(self, max: usize) -> InFlightLimit<Self>
+    where
+        Self: Sized,
+    {
+        InFlightLimit::new(self, max)
+    }
+
+    /// Fail fast, rather than wait, when the inner service is at capacity.
+    fn load_shed(self) -> LoadShed<Self>
+    where
+        Self: Sized,
+    {
+        LoadShed::new(self)
+    }
+
+    /// Spawn this service onto a worker task, decoupling `poll_ready` and
+    /// allowing it to be shared across many callers via a cheaply `Clone`
+    /// handle.
+    fn buffer(self, capacity: usize) -> Result<Buffer<Self, Request>, tower_buffer::SpawnError>
+    where
+        Self: Sized + Send + 'static,
+        Self::Future: Send,
+        Request: Send + 'static,
+    {
+        Buffer::new(self, capacity)
+    }
+
+    /// Map this service's response to a different type, synchronously.
+    fn map<F, Response2>(self, f: F) -> Map<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Response) -> Response2 + Clone,
+    {
+        Map::new(self, f)
+    }
+
+    /// Chain an asynchronous post-processing step onto every call, seeing
+    /// both successful responses and errors.
+    ///
+    /// This is useful for normalizing the heterogeneous error types
+    /// produced by other middleware in this crate family into a single
+    /// type.
+    fn then<F, Fut>(self, f: F) -> Then<Self, F>
+    where
+        Self: Sized,
+        Self::Error: Into<Fut::Error>,
+        F: FnMut(Result<Self::Response, Self::Error>) -> Fut + Clone,
+        Fut: Future,
+    {
+        Then::new(self, f)
+    }
+}
+
+impl<T: ?Sized, Request> ServiceExt<Request> for T where T: Service<Request> {}
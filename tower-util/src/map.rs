@@ -0,0 +1,75 @@
+use futures::{Async, Future, Poll};
+use tower_service::Service;
+
+/// Service returned by [`ServiceExt::map`](crate::ServiceExt::map).
+#[derive(Debug, Clone)]
+pub struct Map<S, F> {
+    inner: S,
+    f: F,
+}
+
+impl<S, F> Map<S, F> {
+    pub(crate) fn new(inner: S, f: F) -> Self {
+        Map { inner, f }
+    }
+
+    /// Get a reference to the inner service
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner service
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume `self`, returning the inner service
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, F, Request, Response2> Service<Request> for Map<S, F>
+where
+    S: Service<Request>,
+    F: FnMut(S::Response) -> Response2 + Clone,
+{
+    type Response = Response2;
+    type Error = S::Error;
+    type Future = MapFuture<S::Future, F>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.inner.poll_ready()
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        MapFuture::new(self.inner.call(request), self.f.clone())
+    }
+}
+
+/// Future returned by [`Map`].
+#[derive(Debug)]
+pub struct MapFuture<T, F> {
+    inner: T,
+    f: F,
+}
+
+impl<T, F> MapFuture<T, F> {
+    fn new(inner: T, f: F) -> Self {
+        MapFuture { inner, f }
+    }
+}
+
+impl<T, F, Response2> Future for MapFuture<T, F>
+where
+    T: Future,
+    F: FnMut(T::Item) -> Response2,
+{
+    type Item = Response2;
+    type Error = T::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let item = try_ready!(self.inner.poll());
+        Ok(Async::Ready((self.f)(item)))
+    }
+}
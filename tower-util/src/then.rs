@@ -0,0 +1,102 @@
+use futures::{Async, Future, Poll};
+use tower_service::Service;
+
+/// Service returned by [`ServiceExt::then`](crate::ServiceExt::then).
+#[derive(Debug, Clone)]
+pub struct Then<S, F> {
+    inner: S,
+    f: F,
+}
+
+impl<S, F> Then<S, F> {
+    pub(crate) fn new(inner: S, f: F) -> Self {
+        Then { inner, f }
+    }
+
+    /// Get a reference to the inner service
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner service
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Consume `self`, returning the inner service
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, F, Request, Fut> Service<Request> for Then<S, F>
+where
+    S: Service<Request>,
+    S::Error: Into<Fut::Error>,
+    F: FnMut(Result<S::Response, S::Error>) -> Fut + Clone,
+    Fut: Future,
+{
+    type Response = Fut::Item;
+    type Error = Fut::Error;
+    type Future = ThenFuture<S::Future, F, Fut>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.inner.poll_ready().map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        ThenFuture::new(self.inner.call(request), self.f.clone())
+    }
+}
+
+/// Future returned by [`Then`].
+#[derive(Debug)]
+pub struct ThenFuture<T, F, Fut> {
+    state: State<T, Fut>,
+    f: Option<F>,
+}
+
+#[derive(Debug)]
+enum State<T, Fut> {
+    Called(T),
+    Then(Fut),
+}
+
+impl<T, F, Fut> ThenFuture<T, F, Fut> {
+    fn new(inner: T, f: F) -> Self {
+        ThenFuture {
+            state: State::Called(inner),
+            f: Some(f),
+        }
+    }
+}
+
+impl<T, F, Fut> Future for ThenFuture<T, F, Fut>
+where
+    T: Future,
+    F: FnMut(Result<T::Item, T::Error>) -> Fut,
+    Fut: Future,
+{
+    type Item = Fut::Item;
+    type Error = Fut::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let next = match self.state {
+                State::Called(ref mut fut) => {
+                    let result = match fut.poll() {
+                        Ok(Async::NotReady) => return Ok(Async::NotReady),
+                        Ok(Async::Ready(v)) => Ok(v),
+                        Err(e) => Err(e),
+                    };
+
+                    let mut f = self.f.take().expect("ThenFuture polled after completion");
+                    State::Then(f(result))
+                }
+                State::Then(ref mut fut) => return fut.poll(),
+            };
+
+            self.state = next;
+        }
+    }
+}